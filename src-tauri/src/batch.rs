@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use image_blp::convert::image_to_blp;
+use image_blp::encode::encode_blp as blp_encode;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tracing::{info, warn};
+
+use crate::encode_options::EncodeOptions;
+
+const SUPPORTED_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "tga", "tiff", "tif"];
+
+/// 批量转换的输入来源
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BatchSource {
+    /// 递归扫描目录(含所有子目录)下所有受支持扩展名的图片
+    Directory { path: String },
+    /// 显式给定的文件路径列表,不做扩展名过滤
+    Files { paths: Vec<String> },
+}
+
+/// 随 `blp://progress` 事件下发给前端的单文件进度
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchProgress {
+    Converting { file: String, index: usize, total: usize },
+    Success { file: String },
+    Failed { file: String, error: String },
+}
+
+/// 批量转换结束后返回给调用方的汇总结果
+#[derive(Debug, Default, Serialize)]
+pub struct BatchSummary {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<(String, String)>,
+}
+
+/// 将 `source` 指定的图片(整棵目录树或显式文件列表)转换为 BLP,写入 `output_dir`
+///
+/// 单个文件失败不会中断整个批次,而是记录进 `BatchSummary::failed`。
+/// 每个文件开始/结束转换时都会通过 `blp://progress` 事件通知前端,
+/// 以便展示进度条而不阻塞 WebView。
+pub async fn convert_batch(
+    app: AppHandle,
+    source: BatchSource,
+    output_dir: PathBuf,
+    options: EncodeOptions,
+) -> Result<BatchSummary, String> {
+    tokio::fs::create_dir_all(&output_dir)
+        .await
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    // 目录遍历是阻塞的文件系统调用,深层目录树会卡住异步运行时,放到
+    // 阻塞线程池执行以保持 WebView 响应
+    let files = {
+        let source = source.clone();
+        tokio::task::spawn_blocking(move || collect_source_files(&source))
+            .await
+            .map_err(|e| format!("Failed to scan source: {}", e))??
+    };
+    let total = files.len();
+    info!(total, "starting batch BLP conversion");
+
+    let mut summary = BatchSummary::default();
+
+    for (index, file) in files.into_iter().enumerate() {
+        let file_name = file.display().to_string();
+        let _ = app.emit(
+            "blp://progress",
+            BatchProgress::Converting {
+                file: file_name.clone(),
+                index,
+                total,
+            },
+        );
+
+        match convert_one(&file, &output_dir, &options).await {
+            Ok(()) => {
+                summary.succeeded.push(file_name.clone());
+                let _ = app.emit("blp://progress", BatchProgress::Success { file: file_name });
+            }
+            Err(error) => {
+                warn!(file = %file.display(), %error, "failed to convert file");
+                summary.failed.push((file_name.clone(), error.clone()));
+                let _ = app.emit("blp://progress", BatchProgress::Failed { file: file_name, error });
+            }
+        }
+    }
+
+    info!(
+        succeeded = summary.succeeded.len(),
+        failed = summary.failed.len(),
+        "batch BLP conversion finished"
+    );
+
+    Ok(summary)
+}
+
+fn collect_source_files(source: &BatchSource) -> Result<Vec<PathBuf>, String> {
+    match source {
+        BatchSource::Directory { path } => walk_directory(Path::new(path)),
+        BatchSource::Files { paths } => Ok(paths.iter().map(PathBuf::from).collect()),
+    }
+}
+
+/// 递归遍历目录,收集所有受支持扩展名的文件,不跳过子目录
+fn walk_directory(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+
+            let is_supported = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| SUPPORTED_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                .unwrap_or(false);
+
+            if is_supported {
+                files.push(path);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+async fn convert_one(path: &Path, output_dir: &Path, options: &EncodeOptions) -> Result<(), String> {
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    // 解码/转换/编码都是 CPU 密集型工作,放到阻塞线程池执行,
+    // 避免单个大文件卡住整个异步运行时
+    let path_owned = path.to_path_buf();
+    let options = options.clone();
+    let encoded = tokio::task::spawn_blocking(move || encode_to_blp(&path_owned, &bytes, &options))
+        .await
+        .map_err(|e| format!("Conversion task panicked: {}", e))??;
+
+    let file_stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| format!("Invalid file name: {}", path.display()))?;
+    let out_path = output_dir.join(format!("{}.blp", file_stem));
+
+    tokio::fs::write(&out_path, encoded)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", out_path.display(), e))?;
+
+    Ok(())
+}
+
+/// 解码、转换并编码为 BLP 字节,纯 CPU 工作,不做任何 IO
+fn encode_to_blp(path: &Path, bytes: &[u8], options: &EncodeOptions) -> Result<Vec<u8>, String> {
+    let img = image::load_from_memory(bytes)
+        .map_err(|e| format!("Failed to decode {}: {}", path.display(), e))?;
+
+    let blp = image_to_blp(
+        img,
+        options.generate_mipmaps,
+        options.container.clone().into(),
+        options.filter.into(),
+    )
+    .map_err(|e| format!("Failed to convert {}: {}", path.display(), e))?;
+
+    blp_encode(&blp).map_err(|e| format!("Failed to encode {}: {}", path.display(), e))
+}
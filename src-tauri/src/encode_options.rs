@@ -0,0 +1,149 @@
+use image_blp::convert::{BlpOldFormat, BlpTarget, FilterType};
+use serde::Deserialize;
+
+/// `encode_blp` 可选的像素过滤器,对应 `image::imageops::FilterType`
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOption {
+    #[default]
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    Lanczos3,
+}
+
+impl From<FilterOption> for FilterType {
+    fn from(filter: FilterOption) -> Self {
+        match filter {
+            FilterOption::Nearest => FilterType::Nearest,
+            FilterOption::Triangle => FilterType::Triangle,
+            FilterOption::CatmullRom => FilterType::CatmullRom,
+            FilterOption::Gaussian => FilterType::Gaussian,
+            FilterOption::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+/// BLP1 容器内的像素格式,魔兽争霸 3 使用这一代容器
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Blp1Format {
+    /// 内嵌 JPEG 数据,WC3 贴图最常见的格式
+    Jpeg { has_alpha: bool },
+    /// 256 色调色板 + 可选 alpha 通道,体积更小但画质较差
+    Paletted { alpha_bits: u8 },
+}
+
+/// BLP2 容器内的像素格式,魔兽世界使用这一代容器
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Blp2Format {
+    Dxt1 { has_alpha: bool },
+    Dxt3 { has_alpha: bool },
+    Dxt5 { has_alpha: bool },
+    /// 未压缩的 A8R8G8B8 原始像素
+    Raw,
+}
+
+/// 选择输出容器版本,WC3 用 BLP1,WoW 用 BLP2
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "container", rename_all = "snake_case")]
+pub enum EncodeContainer {
+    Blp1 { format: Blp1Format },
+    Blp2 { format: Blp2Format },
+}
+
+impl Default for EncodeContainer {
+    fn default() -> Self {
+        EncodeContainer::Blp1 {
+            format: Blp1Format::Jpeg { has_alpha: true },
+        }
+    }
+}
+
+impl From<EncodeContainer> for BlpTarget {
+    fn from(container: EncodeContainer) -> Self {
+        match container {
+            EncodeContainer::Blp1 {
+                format: Blp1Format::Jpeg { has_alpha },
+            } => BlpTarget::Blp1(BlpOldFormat::Jpeg { has_alpha }),
+            EncodeContainer::Blp1 {
+                format: Blp1Format::Paletted { alpha_bits },
+            } => BlpTarget::Blp1(BlpOldFormat::Raw1 { alpha_bits }),
+            EncodeContainer::Blp2 {
+                format: Blp2Format::Dxt1 { has_alpha },
+            } => BlpTarget::Blp2(image_blp::convert::Blp2Format::Dxt1 { has_alpha }),
+            EncodeContainer::Blp2 {
+                format: Blp2Format::Dxt3 { has_alpha },
+            } => BlpTarget::Blp2(image_blp::convert::Blp2Format::Dxt3 { has_alpha }),
+            EncodeContainer::Blp2 {
+                format: Blp2Format::Dxt5 { has_alpha },
+            } => BlpTarget::Blp2(image_blp::convert::Blp2Format::Dxt5 { has_alpha }),
+            EncodeContainer::Blp2 {
+                format: Blp2Format::Raw,
+            } => BlpTarget::Blp2(image_blp::convert::Blp2Format::Raw3),
+        }
+    }
+}
+
+/// `encode_blp` 的完整编码选项,由前端序列化传入
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EncodeOptions {
+    #[serde(default)]
+    pub container: EncodeContainer,
+    #[serde(default)]
+    pub generate_mipmaps: bool,
+    #[serde(default)]
+    pub filter: FilterOption,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_container_is_blp1_jpeg_with_alpha() {
+        let target: BlpTarget = EncodeContainer::default().into();
+        assert!(matches!(
+            target,
+            BlpTarget::Blp1(BlpOldFormat::Jpeg { has_alpha: true })
+        ));
+    }
+
+    #[test]
+    fn blp1_paletted_maps_to_raw1() {
+        let container = EncodeContainer::Blp1 {
+            format: Blp1Format::Paletted { alpha_bits: 1 },
+        };
+        let target: BlpTarget = container.into();
+        assert!(matches!(
+            target,
+            BlpTarget::Blp1(BlpOldFormat::Raw1 { alpha_bits: 1 })
+        ));
+    }
+
+    #[test]
+    fn blp2_dxt_variants_carry_has_alpha_through() {
+        let container = EncodeContainer::Blp2 {
+            format: Blp2Format::Dxt3 { has_alpha: true },
+        };
+        let target: BlpTarget = container.into();
+        assert!(matches!(
+            target,
+            BlpTarget::Blp2(image_blp::convert::Blp2Format::Dxt3 { has_alpha: true })
+        ));
+    }
+
+    #[test]
+    fn blp2_raw_maps_to_raw3() {
+        let container = EncodeContainer::Blp2 {
+            format: Blp2Format::Raw,
+        };
+        let target: BlpTarget = container.into();
+        assert!(matches!(
+            target,
+            BlpTarget::Blp2(image_blp::convert::Blp2Format::Raw3)
+        ));
+    }
+}
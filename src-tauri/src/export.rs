@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::Path;
+
+use image::{DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 除 BLP 外,同一张解码图片还可以附带导出的格式
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Png,
+    Jpeg,
+    WebP,
+    Tga,
+    Qoi,
+}
+
+impl ExportFormat {
+    fn image_format(self) -> ImageFormat {
+        match self {
+            ExportFormat::Png => ImageFormat::Png,
+            ExportFormat::Jpeg => ImageFormat::Jpeg,
+            ExportFormat::WebP => ImageFormat::WebP,
+            ExportFormat::Tga => ImageFormat::Tga,
+            ExportFormat::Qoi => ImageFormat::Qoi,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            ExportFormat::Png => "png",
+            ExportFormat::Jpeg => "jpeg",
+            ExportFormat::WebP => "webp",
+            ExportFormat::Tga => "tga",
+            ExportFormat::Qoi => "qoi",
+        }
+    }
+
+    /// 归一化为该格式编码器实际支持的色彩类型,避免 JPEG 拒绝
+    /// alpha 通道、QOI/WebP 拒绝灰度或 16-bit 等 `Unsupported` 错误
+    fn normalize(self, img: &DynamicImage) -> DynamicImage {
+        match self {
+            ExportFormat::Jpeg => DynamicImage::ImageRgb8(img.to_rgb8()),
+            ExportFormat::Png => img.clone(),
+            ExportFormat::WebP | ExportFormat::Qoi | ExportFormat::Tga => {
+                DynamicImage::ImageRgba8(img.to_rgba8())
+            }
+        }
+    }
+}
+
+/// 一个已写入磁盘的产物:路径与内容哈希
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedFile {
+    pub path: String,
+    pub sha256: String,
+}
+
+/// 产物清单:格式名 -> 导出结果,调用方可据此判断哪些文件未变化而跳过重写
+pub type ExportManifest = HashMap<String, ExportedFile>;
+
+/// `export_formats` 的结果:成功产物的清单,以及单个格式编码失败时的原因。
+/// 一个格式编码或写入失败不会中断其它格式的导出。
+#[derive(Debug, Default, Serialize)]
+pub struct ExportOutcome {
+    pub manifest: ExportManifest,
+    pub failed: Vec<(String, String)>,
+}
+
+/// 以内容的 SHA-256 作为文件名,将 `bytes` 写入 `output_dir/<hash>.<extension>`
+///
+/// 若目标文件已存在则认为内容未变化,直接跳过写入,实现跨批次的去重。
+pub fn write_content_addressed(
+    output_dir: &Path,
+    extension: &str,
+    bytes: &[u8],
+) -> Result<ExportedFile, String> {
+    let sha256 = hex_sha256(bytes);
+    let path = output_dir.join(format!("{}.{}", sha256, extension));
+
+    if !path.exists() {
+        std::fs::write(&path, bytes)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    Ok(ExportedFile {
+        path: path.display().to_string(),
+        sha256,
+    })
+}
+
+/// 将解码后的图片编码为所请求的附加格式,内容寻址写入磁盘并汇总进清单。
+/// 单个格式编码失败(如色彩类型不受支持)只会记录进 `failed`,不会影响其它格式。
+pub fn export_formats(
+    img: &DynamicImage,
+    output_dir: &Path,
+    formats: &[ExportFormat],
+) -> Result<ExportOutcome, String> {
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let mut outcome = ExportOutcome::default();
+    for format in formats {
+        let normalized = format.normalize(img);
+        let mut encoded = Cursor::new(Vec::new());
+
+        let result = normalized
+            .write_to(&mut encoded, format.image_format())
+            .map_err(|e| format!("Failed to encode {}: {}", format.key(), e))
+            .and_then(|()| write_content_addressed(output_dir, format.key(), &encoded.into_inner()));
+
+        match result {
+            Ok(exported) => {
+                outcome.manifest.insert(format.key().to_string(), exported);
+            }
+            Err(error) => outcome.failed.push((format.key().to_string(), error)),
+        }
+    }
+
+    Ok(outcome)
+}
+
+fn hex_sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_content_addressed_skips_rewrite_when_file_already_exists() {
+        let dir = std::env::temp_dir().join(format!(
+            "pds_pre_view_export_dedup_{}_{}",
+            std::process::id(),
+            line!()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let bytes = b"same content, hashed the same way every time";
+        let first = write_content_addressed(&dir, "bin", bytes).unwrap();
+
+        // Mutate the hash-named file out of band; a second write with the
+        // *same* bytes must see the path already exists and skip rewriting it.
+        std::fs::write(&first.path, b"mutated-out-of-band").unwrap();
+
+        let second = write_content_addressed(&dir, "bin", bytes).unwrap();
+        assert_eq!(first.path, second.path);
+        assert_eq!(first.sha256, second.sha256);
+        assert_eq!(
+            std::fs::read(&second.path).unwrap(),
+            b"mutated-out-of-band"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+
+/// 图片的来源,统一归一化为字节后再交给 `image::load_from_memory`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ImageSource {
+    /// "data:image/png;base64,..." 形式的 data URL
+    DataUrl(String),
+    /// 本地文件路径
+    FilePath(String),
+    /// http(s) 远程地址
+    Url(String),
+    /// 不带 data URL 头的裸 base64 字符串
+    Base64(String),
+}
+
+impl ImageSource {
+    /// 在没有明确来源类型时,根据内容特征猜测来源,
+    /// 让调用方可以直接传一个字符串而不必自己包一层 data URL。
+    ///
+    /// 一个不存在、且本身不是合法 base64 的字符串(如带扩展名的路径)会被识别为
+    /// `FilePath` 而不是悄悄当作 base64,这样调用方看到的是"文件不存在"而不是
+    /// 一头雾水的"base64 解码失败"。不存在但恰好能按 base64 解码的字符串仍归为 `Base64`。
+    pub fn classify(raw: String) -> ImageSource {
+        if raw.starts_with("data:") {
+            ImageSource::DataUrl(raw)
+        } else if raw.starts_with("http://") || raw.starts_with("https://") {
+            ImageSource::Url(raw)
+        } else if std::path::Path::new(&raw).exists() || !is_base64(&raw) {
+            ImageSource::FilePath(raw)
+        } else {
+            ImageSource::Base64(raw)
+        }
+    }
+
+    /// 将来源解析为原始图片字节
+    pub async fn resolve(&self) -> Result<Vec<u8>, String> {
+        match self {
+            ImageSource::DataUrl(data_url) => decode_data_url(data_url),
+            ImageSource::FilePath(path) => tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read file {}: {}", path, e)),
+            ImageSource::Url(url) => fetch_url(url).await,
+            ImageSource::Base64(data) => general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| format!("Failed to decode base64: {}", e)),
+        }
+    }
+}
+
+fn is_base64(raw: &str) -> bool {
+    !raw.is_empty() && general_purpose::STANDARD.decode(raw).is_ok()
+}
+
+fn decode_data_url(data_url: &str) -> Result<Vec<u8>, String> {
+    let base64_data = data_url
+        .split(',')
+        .nth(1)
+        .ok_or("Invalid data URL format")?;
+
+    general_purpose::STANDARD
+        .decode(base64_data)
+        .map_err(|e| format!("Failed to decode base64: {}", e))
+}
+
+async fn fetch_url(url: &str) -> Result<Vec<u8>, String> {
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+
+    response
+        .bytes()
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| format!("Failed to read response body from {}: {}", url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_data_url() {
+        assert!(matches!(
+            ImageSource::classify("data:image/png;base64,AAAA".to_string()),
+            ImageSource::DataUrl(_)
+        ));
+    }
+
+    #[test]
+    fn classify_http_url() {
+        assert!(matches!(
+            ImageSource::classify("https://example.com/texture.png".to_string()),
+            ImageSource::Url(_)
+        ));
+    }
+
+    #[test]
+    fn classify_existing_file_path() {
+        let path = std::env::temp_dir().join("pds_pre_view_classify_existing.png");
+        std::fs::write(&path, b"fake-image-bytes").unwrap();
+
+        assert!(matches!(
+            ImageSource::classify(path.to_str().unwrap().to_string()),
+            ImageSource::FilePath(_)
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn classify_missing_path_like_string_as_file_path_not_base64() {
+        // Not valid base64 (`.` is outside the base64 alphabet),
+        // and the file doesn't exist — must surface as a file-not-found error,
+        // not a confusing base64 decode error.
+        assert!(matches!(
+            ImageSource::classify("/definitely/missing/texture.png".to_string()),
+            ImageSource::FilePath(_)
+        ));
+    }
+
+    #[test]
+    fn classify_bare_base64() {
+        let encoded = general_purpose::STANDARD.encode(b"hello world");
+        assert!(matches!(
+            ImageSource::classify(encoded),
+            ImageSource::Base64(_)
+        ));
+    }
+}
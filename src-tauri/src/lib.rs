@@ -1,6 +1,23 @@
+mod batch;
+mod encode_options;
+mod export;
+mod image_source;
+mod pipeline;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
 use base64::{engine::general_purpose, Engine as _};
-use image_blp::convert::{image_to_blp, BlpOldFormat, BlpTarget, FilterType};
+use image::ImageFormat;
+use image_blp::convert::{blp_to_image, image_to_blp};
 use image_blp::encode::encode_blp as blp_encode;
+use image_blp::parser::parse_blp;
+
+use batch::{BatchSource, BatchSummary};
+use encode_options::EncodeOptions;
+use export::{ExportFormat, ExportOutcome};
+use image_source::ImageSource;
+use pipeline::ImageOp;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -8,40 +25,34 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-/// 将 base64 图片数据编码为 BLP 格式
+/// 将图片编码为 BLP 格式
 ///
 /// # 参数
-/// * `image_data_url` - base64 编码的图片数据 URL (如 "data:image/png;base64,...")
+/// * `image_source` - 图片来源:data URL、本地文件路径、http(s) URL 或裸 base64 字符串
+/// * `options` - 编码选项,省略时默认编码为 BLP1 JPEG (魔兽争霸3标准格式)
 ///
 /// # 返回
 /// * `Ok(Vec<u8>)` - BLP 格式的字节数组
 /// * `Err(String)` - 错误信息
 #[tauri::command]
-fn encode_blp(image_data_url: String) -> Result<Vec<u8>, String> {
-    // 解析 data URL,提取 base64 部分
-    let base64_data = image_data_url
-        .split(',')
-        .nth(1)
-        .ok_or("Invalid data URL format")?;
-
-    // 解码 base64
-    let image_bytes = general_purpose::STANDARD
-        .decode(base64_data)
-        .map_err(|e| format!("Failed to decode base64: {}", e))?;
-
-    // 使用 image crate 加载图片
+async fn encode_blp(
+    image_source: String,
+    options: Option<EncodeOptions>,
+) -> Result<Vec<u8>, String> {
+    // 根据来源特征归一化为原始字节,再交给 image crate 加载
+    let image_bytes = ImageSource::classify(image_source).resolve().await?;
+
     let img = image::load_from_memory(&image_bytes)
         .map_err(|e| format!("Failed to load image: {}", e))?;
 
-    // 转换为 BLP (使用 BLP1 Jpeg 格式,魔兽争霸3标准格式)
-    let make_mipmaps = false; // 不生成 mipmap 以简化
+    let options = options.unwrap_or_default();
+
+    // 转换为 BLP,容器版本与像素格式由调用方选择
     let blp = image_to_blp(
         img,
-        make_mipmaps,
-        BlpTarget::Blp1(BlpOldFormat::Jpeg {
-            has_alpha: true, // 支持 alpha 通道
-        }),
-        FilterType::Nearest,
+        options.generate_mipmaps,
+        options.container.into(),
+        options.filter.into(),
     )
     .map_err(|e| format!("Failed to convert to BLP: {}", e))?;
 
@@ -51,13 +62,157 @@ fn encode_blp(image_data_url: String) -> Result<Vec<u8>, String> {
     Ok(encoded)
 }
 
+/// 对图片依次应用一组有序变换(缩放、裁剪、水印、翻转等),再编码为 BLP
+///
+/// # 参数
+/// * `image_source` - 图片来源:data URL、本地文件路径、http(s) URL 或裸 base64 字符串
+/// * `ops` - 按顺序执行的变换列表,前一步的输出是下一步的输入
+/// * `options` - 编码选项,省略时默认编码为 BLP1 JPEG
+///
+/// # 返回
+/// * `Ok(Vec<u8>)` - BLP 格式的字节数组
+/// * `Err(String)` - 错误信息
+#[tauri::command]
+async fn encode_blp_with_pipeline(
+    image_source: String,
+    ops: Vec<ImageOp>,
+    options: Option<EncodeOptions>,
+) -> Result<Vec<u8>, String> {
+    let image_bytes = ImageSource::classify(image_source).resolve().await?;
+
+    let img = image::load_from_memory(&image_bytes)
+        .map_err(|e| format!("Failed to load image: {}", e))?;
+
+    let img = pipeline::apply_pipeline(img, &ops)?;
+
+    let options = options.unwrap_or_default();
+    let blp = image_to_blp(
+        img,
+        options.generate_mipmaps,
+        options.container.into(),
+        options.filter.into(),
+    )
+    .map_err(|e| format!("Failed to convert to BLP: {}", e))?;
+
+    blp_encode(&blp).map_err(|e| format!("Failed to encode BLP: {}", e))
+}
+
+/// 将 BLP 格式字节解码为可在前端预览的 PNG data URL
+///
+/// # 参数
+/// * `blp_data` - BLP 格式的字节数组
+/// * `mipmap_level` - 要解码的 mipmap 层级,默认为 0 (原始分辨率)
+///
+/// # 返回
+/// * `Ok(String)` - "data:image/png;base64,..." 形式的 data URL
+/// * `Err(String)` - 错误信息
+#[tauri::command]
+fn decode_blp(blp_data: Vec<u8>, mipmap_level: Option<u32>) -> Result<String, String> {
+    let mipmap_level = mipmap_level.unwrap_or(0) as usize;
+
+    // 解析 BLP 文件结构
+    let blp = parse_blp(&blp_data).map_err(|e| format!("Failed to parse BLP: {}", e))?;
+
+    // 取出指定 mipmap 层级对应的图片
+    let img =
+        blp_to_image(&blp, mipmap_level).map_err(|e| format!("Failed to decode BLP: {}", e))?;
+
+    // 重新编码为 PNG 以便前端直接预览
+    let mut png_bytes = Cursor::new(Vec::new());
+    img.write_to(&mut png_bytes, ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+
+    let base64_data = general_purpose::STANDARD.encode(png_bytes.into_inner());
+
+    Ok(format!("data:image/png;base64,{}", base64_data))
+}
+
+/// 批量将一批图片转换为 BLP,转换过程中通过 `blp://progress`
+/// 事件上报每个文件的进度,供前端展示进度条
+///
+/// # 参数
+/// * `source` - 待转换图片来源:递归扫描的目录,或显式的文件路径列表
+/// * `output_dir` - BLP 输出目录,不存在时会自动创建
+/// * `options` - 编码选项,省略时默认编码为 BLP1 JPEG
+///
+/// # 返回
+/// * `Ok(BatchSummary)` - 成功/失败的文件清单,单个文件失败不会中断整批转换
+/// * `Err(String)` - 目录级别的错误信息(如源目录不存在)
+#[tauri::command]
+async fn batch_convert_to_blp(
+    app: tauri::AppHandle,
+    source: BatchSource,
+    output_dir: String,
+    options: Option<EncodeOptions>,
+) -> Result<BatchSummary, String> {
+    batch::convert_batch(
+        app,
+        source,
+        PathBuf::from(output_dir),
+        options.unwrap_or_default(),
+    )
+    .await
+}
+
+/// 将图片编码为 BLP,并可选同时导出 PNG/JPEG/WebP/TGA/QOI 等伴生格式,
+/// 所有产物都以内容的 SHA-256 作为文件名写入 `output_dir`,便于跨批次去重
+///
+/// # 参数
+/// * `image_source` - 图片来源:data URL、本地文件路径、http(s) URL 或裸 base64 字符串
+/// * `output_dir` - 产物输出目录,不存在时会自动创建
+/// * `formats` - 除 BLP 外还要导出的格式列表
+/// * `options` - BLP 编码选项,省略时默认编码为 BLP1 JPEG
+///
+/// # 返回
+/// * `Ok(ExportOutcome)` - `manifest` 为格式名到 `{path, sha256}` 的映射(固定包含
+///   `"blp"`),`failed` 列出因色彩类型不受支持等原因跳过的格式,不会中断其它格式
+/// * `Err(String)` - 来源解析、图片加载、BLP 编码/写入失败或目录级别的错误信息
+#[tauri::command]
+async fn export_image(
+    image_source: String,
+    output_dir: String,
+    formats: Vec<ExportFormat>,
+    options: Option<EncodeOptions>,
+) -> Result<ExportOutcome, String> {
+    let image_bytes = ImageSource::classify(image_source).resolve().await?;
+    let img = image::load_from_memory(&image_bytes)
+        .map_err(|e| format!("Failed to load image: {}", e))?;
+
+    let output_dir = PathBuf::from(output_dir);
+    let mut outcome = export::export_formats(&img, &output_dir, &formats)?;
+
+    let options = options.unwrap_or_default();
+    let blp = image_to_blp(
+        img,
+        options.generate_mipmaps,
+        options.container.into(),
+        options.filter.into(),
+    )
+    .map_err(|e| format!("Failed to convert to BLP: {}", e))?;
+    let encoded = blp_encode(&blp).map_err(|e| format!("Failed to encode BLP: {}", e))?;
+
+    let blp_exported = export::write_content_addressed(&output_dir, "blp", &encoded)?;
+    outcome.manifest.insert("blp".to_string(), blp_exported);
+
+    Ok(outcome)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    tracing_subscriber::fmt::init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .invoke_handler(tauri::generate_handler![greet, encode_blp])
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            encode_blp,
+            encode_blp_with_pipeline,
+            decode_blp,
+            batch_convert_to_blp,
+            export_image
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,107 @@
+use base64::{engine::general_purpose, Engine as _};
+use image::DynamicImage;
+use serde::Deserialize;
+
+use crate::encode_options::FilterOption;
+
+/// 镜像翻转方向
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlipDirection {
+    Horizontal,
+    Vertical,
+}
+
+/// 应用在解码后的 `DynamicImage` 上的单步操作,按数组顺序依次执行
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ImageOp {
+    Resize {
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        filter: FilterOption,
+    },
+    Crop {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    Watermark {
+        /// 水印图片,仅接受 "data:image/...;base64,..." 形式的 data URL
+        data_url: String,
+        x: i64,
+        y: i64,
+        /// 叠加不透明度,范围 0.0 ~ 1.0
+        opacity: f32,
+    },
+    Flip {
+        direction: FlipDirection,
+    },
+}
+
+/// 依次对图片应用一组有序操作,上一步的输出是下一步的输入
+pub fn apply_pipeline(image: DynamicImage, ops: &[ImageOp]) -> Result<DynamicImage, String> {
+    ops.iter().try_fold(image, |img, op| apply_op(img, op))
+}
+
+fn apply_op(img: DynamicImage, op: &ImageOp) -> Result<DynamicImage, String> {
+    match op {
+        ImageOp::Resize {
+            width,
+            height,
+            filter,
+        } => Ok(img.resize_exact(*width, *height, (*filter).into())),
+        ImageOp::Crop {
+            x,
+            y,
+            width,
+            height,
+        } => Ok(img.crop_imm(*x, *y, *width, *height)),
+        ImageOp::Watermark {
+            data_url,
+            x,
+            y,
+            opacity,
+        } => watermark(img, data_url, *x, *y, *opacity),
+        ImageOp::Flip { direction } => Ok(match direction {
+            FlipDirection::Horizontal => DynamicImage::ImageRgba8(image::imageops::flip_horizontal(&img)),
+            FlipDirection::Vertical => DynamicImage::ImageRgba8(image::imageops::flip_vertical(&img)),
+        }),
+    }
+}
+
+/// 将一张 data URL 图片以给定不透明度叠加到 `base` 的 (x, y) 位置
+fn watermark(base: DynamicImage, data_url: &str, x: i64, y: i64, opacity: f32) -> Result<DynamicImage, String> {
+    let base64_data = data_url
+        .split(',')
+        .nth(1)
+        .ok_or("Invalid watermark data URL format")?;
+
+    let mark_bytes = general_purpose::STANDARD
+        .decode(base64_data)
+        .map_err(|e| format!("Failed to decode watermark base64: {}", e))?;
+
+    let mark = image::load_from_memory(&mark_bytes)
+        .map_err(|e| format!("Failed to load watermark image: {}", e))?
+        .to_rgba8();
+
+    let mut base = base.to_rgba8();
+    let opacity = opacity.clamp(0.0, 1.0);
+
+    for (mx, my, mark_pixel) in mark.enumerate_pixels() {
+        let (bx, by) = (x + mx as i64, y + my as i64);
+        if bx < 0 || by < 0 || bx as u32 >= base.width() || by as u32 >= base.height() {
+            continue;
+        }
+
+        let base_pixel = base.get_pixel_mut(bx as u32, by as u32);
+        let alpha = opacity * mark_pixel[3] as f32 / 255.0;
+        for c in 0..3 {
+            base_pixel[c] = (mark_pixel[c] as f32 * alpha + base_pixel[c] as f32 * (1.0 - alpha)) as u8;
+        }
+    }
+
+    Ok(DynamicImage::ImageRgba8(base))
+}